@@ -0,0 +1,23 @@
+use std::iter::FromIterator;
+
+use sudoku_solver::Sudoku;
+
+fn main() {
+  let mut sudoku1 = Sudoku::from_iter(vec![
+    (2, 7),(6, 3),(7, 1),(9, 6),(13, 9),(15, 7),(19, 1),(23, 8),(27, 2),(29, 6),(30, 8),
+    (32, 9),(37, 4),(39, 6),(41, 1),(43, 9),(48, 3),(50, 7),(51, 8),(53, 6),(57, 7),
+    (61, 3),(65, 1),(67, 8),(71, 2),(73, 2),(74, 5),(78, 6)
+  ]);
+  println!("{}", sudoku1);
+  assert!(sudoku1.solve());
+  println!("{}", sudoku1);
+
+  let mut sudoku2 = Sudoku::from_iter(vec![
+    (2,4),(9,9),(10,5),(12,4),(17,8),(22,1),(24,5),(26,6),(28,3),(30,6),
+    (35,5),(37,1),(39,3),(41,8),(43,6),(45,4),(50,5),(52,7),(54,8),(56,9),
+    (58,4),(63,3),(68,2),(70,5),(71,4),(78,2)
+  ]);
+  println!("{}", sudoku2);
+  assert!(sudoku2.solve());
+  println!("{}", sudoku2);
+}
@@ -0,0 +1,38 @@
+use sudoku_solver::PropagationBoard;
+
+fn main() {
+  let config1 = [
+    [7, 0, 6, 0, 4, 0, 9, 0, 0],
+    [0, 0, 0, 1, 6, 2, 0, 7, 0],
+    [5, 0, 3, 0, 0, 0, 1, 0, 4],
+    [0, 5, 0, 6, 0, 4, 0, 1, 0],
+    [4, 3, 0, 0, 0, 0, 0, 2, 6],
+    [0, 6, 0, 3, 0, 9, 0, 4, 0],
+    [3, 0, 4, 0, 0, 0, 6, 0, 8],
+    [0, 7, 0, 8, 3, 6, 0, 0, 0],
+    [0, 0, 1, 0, 9, 0, 2, 0, 7],
+  ];
+  let config2 = [
+    [5, 3, 0, 0, 7, 0, 0, 0, 0],
+    [6, 0, 0, 1, 9, 5, 0, 0, 0],
+    [0, 9, 8, 0, 0, 0, 0, 6, 0],
+    [8, 0, 0, 0, 6, 0, 0, 0, 3],
+    [4, 0, 0, 8, 0, 3, 0, 0, 1],
+    [7, 0, 0, 0, 2, 0, 0, 0, 6],
+    [0, 6, 0, 0, 0, 0, 2, 8, 0],
+    [0, 0, 0, 4, 1, 9, 0, 0, 5],
+    [0, 0, 0, 0, 8, 0, 0, 7, 9]
+  ];
+
+  let mut board1 = PropagationBoard::new(config1);
+
+  println!("{}", board1);
+  board1.solve();
+  println!("{}", board1);
+
+  let mut board2 = PropagationBoard::new(config2);
+
+  println!("{}", board2);
+  board2.solve();
+  println!("{}", board2);
+}
@@ -0,0 +1,35 @@
+use std::collections::HashMap;
+
+/// A generic grid of tiles shared by tile-filling constraint games (Sudoku
+/// and its variants, but generic enough for other grid puzzles). Cells are
+/// addressed by `(row, col)` and left absent from `tiles` while unfilled, so
+/// sparse/partially-solved boards don't need a sentinel "empty" value.
+#[derive(Debug, Clone)]
+pub struct Board {
+  pub width: usize,
+  pub height: usize,
+  pub num_options: usize,
+  pub tiles: HashMap<(usize, usize), usize>,
+}
+
+impl Board {
+  pub fn new(width: usize, height: usize, num_options: usize) -> Self {
+    Board { width, height, num_options, tiles: HashMap::new() }
+  }
+
+  pub fn get(&self, pos: (usize, usize)) -> Option<usize> {
+    self.tiles.get(&pos).copied()
+  }
+
+  pub fn set(&mut self, pos: (usize, usize), value: usize) {
+    self.tiles.insert(pos, value);
+  }
+
+  pub fn clear(&mut self, pos: (usize, usize)) {
+    self.tiles.remove(&pos);
+  }
+
+  pub fn is_filled(&self, pos: (usize, usize)) -> bool {
+    self.tiles.contains_key(&pos)
+  }
+}
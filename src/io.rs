@@ -0,0 +1,108 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::sudoku::{AREA, DIMS};
+
+/// Why a puzzle string failed to parse.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+  /// The single-line format wasn't exactly `DIMS * DIMS` characters long.
+  WrongLength { expected: usize, actual: usize },
+  /// A character outside `1-9`, `0`, or `.` showed up in the single-line format.
+  InvalidDigit(char),
+  /// The CSV format's leading dimensions header wasn't `"9,9"`.
+  UnsupportedDimensions(String),
+  /// A `row,col,value` line didn't parse as three comma-separated integers,
+  /// or one of them was out of range.
+  MalformedRow(String),
+}
+
+impl fmt::Display for ParseError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ParseError::WrongLength { expected, actual } =>
+        write!(f, "expected a {}-character puzzle, got {}", expected, actual),
+      ParseError::InvalidDigit(c) =>
+        write!(f, "'{}' is not a valid digit (expected 1-9, 0, or .)", c),
+      ParseError::UnsupportedDimensions(dims) =>
+        write!(f, "unsupported puzzle dimensions {:?}, only 9,9 is supported", dims),
+      ParseError::MalformedRow(row) =>
+        write!(f, "expected \"row,col,value\", got {:?}", row),
+    }
+  }
+}
+
+impl Error for ParseError {}
+
+/// Parses either the 81-character single-line format (digits `1`-`9` as
+/// givens, `0` or `.` as blanks) or the line-based `row,col,value` CSV
+/// format with a leading `9,9` dimensions header, returning the givens as
+/// `(index, value)` pairs ready for `Sudoku::from_iter`.
+pub(crate) fn parse(s: &str) -> Result<Vec<(usize, u32)>, ParseError> {
+  let trimmed = s.trim();
+
+  if trimmed.lines().count() <= 1 {
+    parse_line(trimmed)
+  } else {
+    parse_csv(trimmed)
+  }
+}
+
+fn parse_line(line: &str) -> Result<Vec<(usize, u32)>, ParseError> {
+  if line.chars().count() != AREA {
+    return Err(ParseError::WrongLength { expected: AREA, actual: line.chars().count() });
+  }
+
+  line.chars().enumerate().filter_map(|(i, c)| match c {
+    '.' | '0' => None,
+    '1'..='9' => Some(Ok((i, c.to_digit(10).unwrap()))),
+    other => Some(Err(ParseError::InvalidDigit(other))),
+  }).collect()
+}
+
+fn parse_csv(s: &str) -> Result<Vec<(usize, u32)>, ParseError> {
+  let mut lines = s.lines().map(str::trim).filter(|l| !l.is_empty());
+
+  let header = lines.next().unwrap_or("");
+  if header != "9,9" {
+    return Err(ParseError::UnsupportedDimensions(header.to_string()));
+  }
+
+  lines.map(|line| {
+    let parts: Vec<&str> = line.split(',').collect();
+    let [row, col, value] = parts[..] else {
+      return Err(ParseError::MalformedRow(line.to_string()));
+    };
+
+    let row: usize = row.trim().parse().map_err(|_| ParseError::MalformedRow(line.to_string()))?;
+    let col: usize = col.trim().parse().map_err(|_| ParseError::MalformedRow(line.to_string()))?;
+    let value: u32 = value.trim().parse().map_err(|_| ParseError::MalformedRow(line.to_string()))?;
+
+    if row == 0 || row > DIMS || col == 0 || col > DIMS || value == 0 || value > DIMS as u32 {
+      return Err(ParseError::MalformedRow(line.to_string()));
+    }
+
+    Ok(((row - 1) * DIMS + (col - 1), value))
+  }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_csv_rejects_out_of_range_value() {
+    assert_eq!(parse("9,9\n1,1,15\n"), Err(ParseError::MalformedRow("1,1,15".to_string())));
+    assert_eq!(parse("9,9\n1,1,0\n"), Err(ParseError::MalformedRow("1,1,0".to_string())));
+  }
+
+  #[test]
+  fn parse_csv_accepts_in_range_value() {
+    assert_eq!(parse("9,9\n1,1,9\n"), Ok(vec![(0, 9)]));
+  }
+
+  #[test]
+  fn parse_line_rejects_wrong_length() {
+    assert_eq!(parse("123"), Err(ParseError::WrongLength { expected: AREA, actual: 3 }));
+  }
+}
@@ -0,0 +1,11 @@
+pub mod board;
+mod io;
+pub mod propagate;
+pub mod rule;
+pub mod sudoku;
+
+pub use board::Board;
+pub use io::ParseError;
+pub use propagate::PropagationBoard;
+pub use rule::Rule;
+pub use sudoku::Sudoku;
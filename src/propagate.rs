@@ -1,22 +1,29 @@
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::fmt;
 
 // representation of an unsolved cell
 struct Cell {
   // set of possible values this cell could be
-  possibilities: HashSet<u32>, 
+  possibilities: HashSet<u32>,
 }
 
 // representation of a solved cell
 struct SolvedCell {
-  // need to keep track of the solved cell's coordinates since 
+  // need to keep track of the solved cell's coordinates since
   // we aren't storing them in a matrix
   x: u32,
   y: u32,
   value: u32,
 }
 
-struct Board {
+/// Constraint-propagation solver: narrows each cell's possibility set from
+/// its solved neighbors until a fixed point is reached (no cell's
+/// possibilities change in a full sweep). Much faster than backtracking on
+/// puzzles simple enough to fully collapse this way, but can stall on harder
+/// ones with cells left ambiguous — pair with `Sudoku::solve_hybrid` to fall
+/// back to the backtracker for whatever's left.
+pub struct PropagationBoard {
   cells: Vec<Vec<Cell>>,
   // keep solved cells in a queue so we process them in FIFO order
   solved_cells: VecDeque<SolvedCell>,
@@ -26,7 +33,7 @@ impl Cell {
   fn new(v: u32) -> Self {
     Cell {
       // init a set contain 1..9 as possibilities is v is 0
-      // otherwise, init a set with just v 
+      // otherwise, init a set with just v
       possibilities: if v == 0 { (1..=9).collect() } else { [v].iter().cloned().collect() }
     }
   }
@@ -48,18 +55,33 @@ impl Cell {
   }
 }
 
-impl Board {
-  fn new(rows: [[u32; 9]; 9]) -> Self {
+impl fmt::Display for PropagationBoard {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for x in 0..self.cells.len() {
+      for y in 0..self.cells[x].len() {
+        let space = if y % 3 == 0 { " " } else { "" };
+        write!(f, "{}{}", space, self.cells[x][y].resolve())?;
+      }
+
+      writeln!(f)?;
+      if (x + 1) % 3 == 0 { writeln!(f)?; }
+    }
+
+    Ok(())
+  }
+}
+
+impl PropagationBoard {
+  pub fn new(rows: [[u32; 9]; 9]) -> Self {
     let mut cells: Vec<Vec<Cell>> = vec![];
     let mut solved_cells: VecDeque<SolvedCell> = VecDeque::new();
 
     // create rows for the board
-    for x in 0..rows.len() {
+    for (x, row) in rows.iter().enumerate() {
       let mut new_row: Vec<Cell> = vec![];
 
       // create columns for the board
-      for y in 0..rows[x].len() {
-        let v = rows[x][y];
+      for (y, &v) in row.iter().enumerate() {
         let cell = Cell::new(v);
         new_row.push(cell);
 
@@ -72,40 +94,32 @@ impl Board {
       cells.push(new_row);
     }
 
-    Board {
+    PropagationBoard {
       cells,
       solved_cells,
     }
   }
 
-  fn print(&self) {
-    for x in 0..self.cells.len() {
-      for y in 0..self.cells[x].len() {
-        let buf = if y % 3 == 0 { " " } else { "" };
-        print!("{}{}", buf, self.cells[x][y].resolve());
-      }
-
-      print!("\n");
-
-      if (x + 1) % 3 == 0 {
-        print!("\n");
-      }
+  /// The digit propagation has pinned down for `(row, col)`, if any.
+  pub fn value_at(&self, row: usize, col: usize) -> Option<u32> {
+    match self.cells[row][col].resolve() {
+      0 => None,
+      v => Some(v),
     }
-
-    println!("*************\n");
   }
 
   // dequeues the next solved cell and checks its corresponding row, column, and block,
-  // reducing any possibilities in its neighboring cells and adding the newly solved 
-  // cells to the queue 
-  fn solve(&mut self) {
+  // reducing any possibilities in its neighboring cells and adding the newly solved
+  // cells to the queue. Draining the queue this way is itself the fixed-point
+  // iteration: it only terminates once a full sweep produces no newly solved cells.
+  pub fn solve(&mut self) {
     while let Some(solved) = self.solved_cells.pop_front() {
       self.reduce_possibilities(solved);
     }
   }
 
   // loops through the row, column, and block of the solved cell and removes the solved cell's
-  // value as a possibility from all its neighboring cells 
+  // value as a possibility from all its neighboring cells
   fn reduce_possibilities(&mut self, solved: SolvedCell) {
     // narrow down horizontal possibilities
     for x in 0..9 {
@@ -128,8 +142,8 @@ impl Board {
     }
   }
 
-  // remove the value as a possibility from the cell at the given coordinates 
-  // if the cell becomes solved, add it to the queue of solved cells 
+  // remove the value as a possibility from the cell at the given coordinates
+  // if the cell becomes solved, add it to the queue of solved cells
   fn reduce_cell_possibilities(&mut self, x: u32, y: u32, v: u32) {
     let cell: &mut Cell = &mut self.cells[x as usize][y as usize];
 
@@ -139,48 +153,11 @@ impl Board {
       // check if the above operation solved the cell
       if cell.is_solved() {
         self.solved_cells.push_back(SolvedCell {
-          x: x as u32,
-          y: y as u32,
+          x,
+          y,
           value: cell.resolve(),
         });
       }
     }
   }
 }
-
-fn main() {
-  let config1 = [
-    [7, 0, 6, 0, 4, 0, 9, 0, 0],
-    [0, 0, 0, 1, 6, 2, 0, 7, 0],
-    [5, 0, 3, 0, 0, 0, 1, 0, 4],
-    [0, 5, 0, 6, 0, 4, 0, 1, 0],
-    [4, 3, 0, 0, 0, 0, 0, 2, 6],
-    [0, 6, 0, 3, 0, 9, 0, 4, 0],
-    [3, 0, 4, 0, 0, 0, 6, 0, 8],
-    [0, 7, 0, 8, 3, 6, 0, 0, 0],
-    [0, 0, 1, 0, 9, 0, 2, 0, 7],
-  ];
-  let config2 = [
-    [5, 3, 0, 0, 7, 0, 0, 0, 0],
-    [6, 0, 0, 1, 9, 5, 0, 0, 0],
-    [0, 9, 8, 0, 0, 0, 0, 6, 0],
-    [8, 0, 0, 0, 6, 0, 0, 0, 3],
-    [4, 0, 0, 8, 0, 3, 0, 0, 1],
-    [7, 0, 0, 0, 2, 0, 0, 0, 6],
-    [0, 6, 0, 0, 0, 0, 2, 8, 0],
-    [0, 0, 0, 4, 1, 9, 0, 0, 5],
-    [0, 0, 0, 0, 8, 0, 0, 7, 9]
-  ];
-
-  let mut board1 = Board::new(config1);
-
-  board1.print();
-  board1.solve();
-  board1.print();
-
-  let mut board2 = Board::new(config2);
-
-  board2.print();
-  board2.solve();
-  board2.print();
-}
\ No newline at end of file
@@ -0,0 +1,10 @@
+use crate::board::Board;
+
+/// A constraint that a candidate placement must satisfy. Implementing this
+/// trait is how a solver is taught new variants (different grid sizes,
+/// diagonal constraints, killer cages, ...) without touching its search core.
+pub trait Rule {
+  /// Returns whether placing `value` at `pos` keeps `board` consistent with
+  /// this constraint. `board` reflects the state *before* the placement.
+  fn is_satisfied(&self, board: &Board, pos: (usize, usize), value: usize) -> bool;
+}
@@ -0,0 +1,529 @@
+use std::fmt;
+use std::iter::FromIterator;
+use std::str::FromStr;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::board::Board;
+use crate::io::{self, ParseError};
+use crate::propagate::PropagationBoard;
+use crate::rule::Rule;
+
+pub(crate) const DIMS: usize = 9;
+pub(crate) const AREA: usize = DIMS * DIMS;
+const ALL_DIGITS: u16 = 0x1FF;
+
+fn coords(i: usize) -> (usize, usize) {
+  (i / DIMS, i % DIMS)
+}
+
+fn block_index(row: usize, col: usize) -> usize {
+  (row / 3) * 3 + (col / 3)
+}
+
+/// Snapshots a `Grid`'s placements into a generic `Board` so `Rule`s can be
+/// evaluated against them.
+fn to_board(grid: &Grid) -> Board {
+  let mut board = Board::new(DIMS, DIMS, DIMS);
+
+  for i in 0..AREA {
+    if grid.cells[i] != 0 {
+      board.set(coords(i), grid.cells[i] as usize);
+    }
+  }
+
+  board
+}
+
+/// The mutable board state the backtracker searches over: the digits placed
+/// so far, plus `row_mask`/`col_mask`/`block_mask` bitsets (bit `d - 1` set
+/// means digit `d` occupies that row/column/3x3 block) that make checking a
+/// candidate an O(1) operation instead of a linear scan. Kept separate from
+/// `Sudoku` so a search (`count_solutions`) can explore a scratch copy
+/// without disturbing the board the caller sees.
+#[derive(Clone, Copy)]
+struct Grid {
+  cells: [u32; AREA],
+  row_mask: [u16; DIMS],
+  col_mask: [u16; DIMS],
+  block_mask: [u16; DIMS],
+}
+
+impl Grid {
+  fn set(&mut self, row: usize, col: usize, value: u32) {
+    let idx = row * DIMS + col;
+    let block = block_index(row, col);
+
+    let prev = self.cells[idx];
+    if prev != 0 {
+      let bit = 1 << (prev - 1);
+      self.row_mask[row] &= !bit;
+      self.col_mask[col] &= !bit;
+      self.block_mask[block] &= !bit;
+    }
+
+    self.cells[idx] = value;
+
+    if value != 0 {
+      let bit = 1 << (value - 1);
+      self.row_mask[row] |= bit;
+      self.col_mask[col] |= bit;
+      self.block_mask[block] |= bit;
+    }
+  }
+
+  /// Complement mask of digits that don't yet conflict with `row`/`col`'s
+  /// house, i.e. the legal candidates for that cell under the row/column/box
+  /// rules alone. Bit `d - 1` set means digit `d` is a candidate.
+  fn candidates(&self, row: usize, col: usize) -> u16 {
+    let block = block_index(row, col);
+    let used = self.row_mask[row] | self.col_mask[col] | self.block_mask[block];
+    !used & ALL_DIGITS
+  }
+
+  /// True if two already-filled cells share a house (row, column, or block)
+  /// and a digit. Each mask bit only records whether a digit occurs
+  /// *somewhere* in a house, so two givens sharing a digit there are
+  /// invisible to `candidates` alone; this compares each house's filled-cell
+  /// count against its mask's popcount to catch it instead.
+  fn has_given_conflicts(&self) -> bool {
+    let mut row_filled = [0u32; DIMS];
+    let mut col_filled = [0u32; DIMS];
+    let mut block_filled = [0u32; DIMS];
+
+    for i in 0..AREA {
+      if self.cells[i] != 0 {
+        let (row, col) = coords(i);
+        row_filled[row] += 1;
+        col_filled[col] += 1;
+        block_filled[block_index(row, col)] += 1;
+      }
+    }
+
+    (0..DIMS).any(|h| {
+      row_filled[h] != self.row_mask[h].count_ones()
+        || col_filled[h] != self.col_mask[h].count_ones()
+        || block_filled[h] != self.block_mask[h].count_ones()
+    })
+  }
+}
+
+impl FromIterator<(usize, u32)> for Grid {
+  fn from_iter<T>(iter: T) -> Self
+    where T: IntoIterator<Item=(usize, u32)>
+  {
+    let mut grid = Grid {
+      cells: [0; AREA],
+      row_mask: [0; DIMS],
+      col_mask: [0; DIMS],
+      block_mask: [0; DIMS],
+    };
+
+    for (i, value) in iter {
+      let (row, col) = coords(i);
+      grid.set(row, col, value);
+    }
+
+    grid
+  }
+}
+
+pub struct Sudoku {
+  grid: Grid,
+  // Extra constraints (diagonals, cages, ...) layered on top of the built-in
+  // row/column/box uniqueness enforced by `Grid`'s masks. Lets users solve
+  // variants without touching the solver core.
+  rules: Vec<Box<dyn Rule>>,
+}
+
+impl fmt::Display for Sudoku {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for i in 0..AREA {
+      let space = if i % 3 == 0 { " " } else { "" };
+      write!(f, "{}{}", space, self.grid.cells[i])?;
+      if (i + 1) % 9 == 0 { writeln!(f)?; }
+      if (i + 1) % 27 == 0 { writeln!(f)?; }
+    }
+
+    writeln!(f)
+  }
+}
+
+impl Sudoku {
+  /// Layers extra constraints (diagonal/X-Sudoku, killer cages, ...) on top
+  /// of the built-in row/column/box uniqueness rules without touching the
+  /// solver itself.
+  pub fn add_rule(&mut self, rule: Box<dyn Rule>) {
+    self.rules.push(rule);
+  }
+
+  /// Complement mask of digits that don't yet conflict with `row`/`col`'s
+  /// house. Bit `d - 1` set means digit `d` is a candidate.
+  pub fn candidates(&self, row: usize, col: usize) -> u16 {
+    self.grid.candidates(row, col)
+  }
+
+  fn is_valid_cell(grid: &Grid, rules: &[Box<dyn Rule>], row: usize, col: usize, value: u32) -> bool {
+    let bit = 1u16 << (value - 1);
+    if grid.candidates(row, col) & bit == 0 {
+      return false;
+    }
+
+    if rules.is_empty() {
+      return true;
+    }
+
+    let board = to_board(grid);
+    rules.iter().all(|rule| rule.is_satisfied(&board, (row, col), value as usize))
+  }
+
+  fn find_solution(grid: &Grid, rules: &[Box<dyn Rule>], row: usize, col: usize) -> Option<u32> {
+    let tried = grid.cells[row * DIMS + col];
+    let mut mask = grid.candidates(row, col);
+    if tried > 0 {
+      mask &= !((1u16 << tried) - 1);
+    }
+
+    while mask != 0 {
+      let value = mask.trailing_zeros() + 1;
+      if rules.is_empty() || Self::is_valid_cell(grid, rules, row, col, value) {
+        return Some(value);
+      }
+      mask &= mask - 1;
+    }
+
+    None
+  }
+
+  /// Picks the empty cell in `remaining` with the fewest legal candidates
+  /// (most-constrained-variable heuristic), falling back to `remaining`'s
+  /// own order on ties.
+  fn choose_mrv_cell(grid: &Grid, remaining: &[usize]) -> usize {
+    *remaining
+      .iter()
+      .min_by_key(|&&cell| {
+        let (row, col) = coords(cell);
+        grid.candidates(row, col).count_ones()
+      })
+      .expect("remaining should be non-empty while cells are left to solve")
+  }
+
+  /// Solves in place. Returns `false` if the cells handed to it have no
+  /// valid completion (e.g. an unsolvable or inconsistent puzzle, or givens
+  /// that already conflict), in which case every cell this call touched is
+  /// cleared back to empty rather than left in a half-searched state.
+  #[must_use]
+  pub fn solve(&mut self) -> bool {
+    if self.grid.has_given_conflicts() {
+      return false;
+    }
+
+    let mut remaining: Vec<usize> = (0..AREA).filter(|&i| self.grid.cells[i] == 0).collect();
+    let total = remaining.len();
+    let mut order: Vec<usize> = Vec::with_capacity(total);
+    let mut need_next_cell = true;
+
+    while order.len() < total {
+      if need_next_cell {
+        let cell = Self::choose_mrv_cell(&self.grid, &remaining);
+        remaining.retain(|&c| c != cell);
+        order.push(cell);
+        need_next_cell = false;
+      }
+
+      let cell = *order.last().unwrap();
+      let (row, col) = coords(cell);
+
+      match Self::find_solution(&self.grid, &self.rules, row, col) {
+        Some(s) => {
+          self.grid.set(row, col, s);
+          need_next_cell = true;
+        },
+        None => {
+          self.grid.set(row, col, 0);
+          remaining.push(cell);
+          order.pop();
+
+          if order.is_empty() {
+            return false;
+          }
+        }
+      }
+    }
+
+    true
+  }
+
+  fn to_grid_array(&self) -> [[u32; DIMS]; DIMS] {
+    let mut grid = [[0u32; DIMS]; DIMS];
+
+    for i in 0..AREA {
+      let (row, col) = coords(i);
+      grid[row][col] = self.grid.cells[i];
+    }
+
+    grid
+  }
+
+  /// Runs constraint-propagation to a fixed point first, then hands whatever
+  /// cells remain ambiguous to the backtracker. Most human-easy puzzles fully
+  /// collapse during propagation with zero backtracking; hard puzzles start
+  /// their search from a much smaller frontier. Returns `false` under the
+  /// same conditions as `solve`.
+  #[must_use]
+  pub fn solve_hybrid(&mut self) -> bool {
+    let mut propagation = PropagationBoard::new(self.to_grid_array());
+    propagation.solve();
+
+    for i in 0..AREA {
+      let (row, col) = coords(i);
+      if let Some(value) = propagation.value_at(row, col) {
+        self.grid.set(row, col, value);
+      }
+    }
+
+    self.solve()
+  }
+
+  /// Serializes the current placements to the 81-character single-line
+  /// format (digits `1`-`9` for filled cells, `.` for blanks).
+  pub fn to_line_string(&self) -> String {
+    self.grid.cells.iter().map(|&v| if v == 0 { '.' } else { char::from_digit(v, 10).unwrap() }).collect()
+  }
+
+  /// Explores the search tree without committing to a solution, counting how
+  /// many distinct solutions exist (short-circuiting once `limit` are
+  /// found). A proper Sudoku has exactly one; use `has_unique_solution` to
+  /// check that directly. Returns `0` without searching if the givens
+  /// themselves already conflict.
+  pub fn count_solutions(&self, limit: usize) -> usize {
+    if self.grid.has_given_conflicts() {
+      return 0;
+    }
+
+    let remaining: Vec<usize> = (0..AREA).filter(|&i| self.grid.cells[i] == 0).collect();
+    let mut grid = self.grid;
+    let mut count = 0;
+
+    self.count_solutions_from(&mut grid, remaining, limit, &mut count);
+    count
+  }
+
+  fn count_solutions_from(&self, grid: &mut Grid, mut remaining: Vec<usize>, limit: usize, count: &mut usize) {
+    if *count >= limit {
+      return;
+    }
+
+    if remaining.is_empty() {
+      *count += 1;
+      return;
+    }
+
+    let cell = Self::choose_mrv_cell(grid, &remaining);
+    remaining.retain(|&c| c != cell);
+    let (row, col) = coords(cell);
+
+    let mut mask = grid.candidates(row, col);
+    while mask != 0 {
+      let value = mask.trailing_zeros() + 1;
+      mask &= mask - 1;
+
+      if !self.rules.is_empty() && !Self::is_valid_cell(grid, &self.rules, row, col, value) {
+        continue;
+      }
+
+      grid.set(row, col, value);
+      self.count_solutions_from(grid, remaining.clone(), limit, count);
+      grid.set(row, col, 0);
+
+      if *count >= limit {
+        return;
+      }
+    }
+  }
+
+  /// A proper Sudoku must have exactly one solution.
+  pub fn has_unique_solution(&self) -> bool {
+    self.count_solutions(2) == 1
+  }
+
+  /// Generates a random puzzle together with its solution. `difficulty` is
+  /// the target number of remaining givens: starting from a randomly-filled
+  /// solved grid, givens are cleared one at a time (in random order),
+  /// re-verifying `has_unique_solution` after each removal, until either
+  /// `difficulty` is reached or no further cell can be cleared without
+  /// losing uniqueness.
+  pub fn generate<R: Rng + ?Sized>(rng: &mut R, difficulty: usize) -> (Sudoku, Sudoku) {
+    let solved = Self::random_solution(rng);
+    let givens: Vec<(usize, u32)> = (0..AREA).map(|i| (i, solved.cells[i])).collect();
+
+    let solution = Sudoku::from_iter(givens.iter().copied());
+    let mut puzzle = Sudoku::from_iter(givens);
+
+    let mut cells: Vec<usize> = (0..AREA).collect();
+    cells.shuffle(rng);
+
+    let mut remaining = AREA;
+    for cell in cells {
+      if remaining <= difficulty {
+        break;
+      }
+
+      let (row, col) = coords(cell);
+      let value = puzzle.grid.cells[cell];
+      puzzle.grid.set(row, col, 0);
+
+      if puzzle.has_unique_solution() {
+        remaining -= 1;
+      } else {
+        puzzle.grid.set(row, col, value);
+      }
+    }
+
+    (puzzle, solution)
+  }
+
+  /// Fills an empty grid via the backtracker with randomized candidate order,
+  /// producing one of the many fully-solved grids uniformly at random rather
+  /// than always the same lexicographically-smallest one.
+  fn random_solution<R: Rng + ?Sized>(rng: &mut R) -> Grid {
+    let mut grid = Grid {
+      cells: [0; AREA],
+      row_mask: [0; DIMS],
+      col_mask: [0; DIMS],
+      block_mask: [0; DIMS],
+    };
+
+    Self::fill_randomized(&mut grid, (0..AREA).collect(), rng);
+    grid
+  }
+
+  fn fill_randomized<R: Rng + ?Sized>(grid: &mut Grid, mut remaining: Vec<usize>, rng: &mut R) -> bool {
+    if remaining.is_empty() {
+      return true;
+    }
+
+    let cell = Self::choose_mrv_cell(grid, &remaining);
+    remaining.retain(|&c| c != cell);
+    let (row, col) = coords(cell);
+
+    let mut digits: Vec<u32> = (1..=9).collect();
+    digits.shuffle(rng);
+
+    for value in digits {
+      if grid.candidates(row, col) & (1u16 << (value - 1)) == 0 {
+        continue;
+      }
+
+      grid.set(row, col, value);
+      if Self::fill_randomized(grid, remaining.clone(), rng) {
+        return true;
+      }
+      grid.set(row, col, 0);
+    }
+
+    false
+  }
+}
+
+impl FromStr for Sudoku {
+  type Err = ParseError;
+
+  /// Parses either the 81-character single-line format (`1`-`9` for givens,
+  /// `0`/`.` for blanks) or the line-based `row,col,value` CSV format with a
+  /// leading `9,9` dimensions header.
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    io::parse(s).map(Sudoku::from_iter)
+  }
+}
+
+impl FromIterator<(usize, u32)> for Sudoku {
+  fn from_iter<T>(iter: T) -> Self
+    where T: IntoIterator<Item=(usize, u32)>
+  {
+    Sudoku {
+      grid: Grid::from_iter(iter),
+      rules: Vec::new(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+
+  use super::*;
+
+  /// X-Sudoku's extra constraint: the main diagonal must also contain each
+  /// digit at most once.
+  struct DiagonalRule;
+
+  impl Rule for DiagonalRule {
+    fn is_satisfied(&self, board: &Board, pos: (usize, usize), value: usize) -> bool {
+      let (row, col) = pos;
+      if row != col {
+        return true;
+      }
+
+      (0..DIMS).filter(|&i| i != row).all(|i| board.get((i, i)) != Some(value))
+    }
+  }
+
+  #[test]
+  fn add_rule_is_respected_end_to_end_by_solve() {
+    let mut sudoku = Sudoku::from_iter(vec![(0, 5)]);
+    sudoku.add_rule(Box::new(DiagonalRule));
+
+    assert!(sudoku.solve());
+
+    let mut diagonal: Vec<u32> = (0..DIMS).map(|i| sudoku.grid.cells[i * DIMS + i]).collect();
+    diagonal.sort_unstable();
+    diagonal.dedup();
+    assert_eq!(diagonal.len(), DIMS);
+  }
+
+  #[test]
+  fn generate_produces_a_uniquely_solvable_puzzle_matching_its_solution() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let (puzzle, solution) = Sudoku::generate(&mut rng, AREA - 40);
+
+    assert!(puzzle.has_unique_solution());
+
+    let mut solved = Sudoku::from_iter((0..AREA).map(|i| (i, puzzle.grid.cells[i])));
+    assert!(solved.solve());
+    assert_eq!(solved.grid.cells, solution.grid.cells);
+  }
+
+  #[test]
+  fn conflicting_givens_in_same_row_have_no_solutions() {
+    let sudoku = Sudoku::from_iter(vec![(0, 5), (1, 5)]);
+    assert_eq!(sudoku.count_solutions(2), 0);
+    assert!(!sudoku.has_unique_solution());
+  }
+
+  #[test]
+  fn solve_rejects_conflicting_givens_instead_of_hanging() {
+    let mut sudoku = Sudoku::from_iter(vec![(0, 5), (1, 5)]);
+    assert!(!sudoku.solve());
+
+    let mut sudoku = Sudoku::from_iter(vec![(0, 5), (1, 5)]);
+    assert!(!sudoku.solve_hybrid());
+  }
+
+  #[test]
+  fn duplicate_digit_in_otherwise_solved_row_is_not_unique() {
+    let solved: Vec<(usize, u32)> = (0..AREA as u32).map(|i| {
+      let (row, col) = coords(i as usize);
+      (i as usize, ((row * 3 + row / 3 + col) % 9) as u32 + 1)
+    }).collect();
+
+    let mut givens = solved.clone();
+    givens[8] = (8, givens[0].1);
+    givens.remove(1);
+
+    let sudoku = Sudoku::from_iter(givens);
+    assert!(!sudoku.has_unique_solution());
+  }
+}